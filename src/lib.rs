@@ -4,11 +4,17 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use id3::{Tag, TagLike, Version};
+use rayon::prelude::*;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CodecParameters, Decoder, DecoderOptions, CODEC_TYPE_AAC};
+use symphonia::core::formats::{FormatOptions, Packet};
 use symphonia::core::io::{MediaSourceStream, ReadOnlySource};
-use symphonia::core::formats::FormatOptions;
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
+/// Length of the analysis frame used for silence detection
+const SILENCE_FRAME_MS: u64 = 20;
+
 /// Information about an audio chunk
 pub struct ChunkInfo {
     /// Start time of the chunk
@@ -18,6 +24,24 @@ pub struct ChunkInfo {
     packets: Vec<usize>, // Indices of packets in the global packets list
 }
 
+/// Strategy used to decide exactly where a chunk boundary falls
+#[derive(Clone, Copy, Default)]
+pub enum SplitMode {
+    /// Cut after the first packet that ends at or past the fixed target duration
+    #[default]
+    FixedDuration,
+    /// Snap the boundary to the nearest detected silence, if one falls within
+    /// `search_window` of the fixed target
+    SilenceAligned {
+        /// How far from the fixed-duration target to look for a silence region
+        search_window: Duration,
+        /// Audio quieter than this (in dBFS) is considered silent
+        threshold_dbfs: f32,
+        /// Minimum length a quiet run must hold to count as a silence region
+        min_silence: Duration,
+    },
+}
+
 /// Configuration options for MP3 splitting
 pub struct SplitOptions<'a> {
     /// Path to the input MP3 file
@@ -28,6 +52,22 @@ pub struct SplitOptions<'a> {
     pub output_dir: &'a Path,
     /// Prefix for output filenames
     pub prefix: &'a str,
+    /// When `true`, also write an `index.m3u8` HLS media playlist referencing
+    /// every output chunk
+    pub emit_hls: bool,
+    /// Override for the playlist's `#EXT-X-TARGETDURATION`, in whole seconds
+    ///
+    /// When `None`, it is computed as the ceiling of the longest chunk's
+    /// duration, as required by the HLS spec.
+    pub hls_target_duration: Option<u32>,
+    /// Strategy used to pick chunk boundaries
+    pub split_mode: SplitMode,
+    /// When `true`, carry the source's encoder delay/padding across chunk boundaries
+    /// so that concatenating all chunks reproduces the original sample-accurate stream
+    pub gapless: bool,
+    /// When `true`, write chunks concurrently on a rayon thread pool instead of
+    /// sequentially
+    pub parallel: bool,
 }
 
 /// Result of MP3 splitting operation
@@ -38,6 +78,710 @@ pub struct SplitResult {
     pub total_duration: Duration,
     /// Paths to generated output files
     pub output_files: Vec<PathBuf>,
+    /// Non-fatal warnings (currently, failed per-chunk ID3 tag writes)
+    pub warnings: Vec<String>,
+}
+
+/// Decodes a packet and returns the dBFS level and actual sample count of each ~20ms
+/// analysis frame it contains
+///
+/// The sample count is returned alongside each level (rather than assumed to be a flat
+/// 20ms) because a packet's sample count rarely divides evenly by the analysis frame
+/// size, leaving a shorter final frame whose real duration the caller needs to track
+/// time accurately.
+fn packet_frame_levels(decoder: &mut dyn Decoder, packet: &Packet, sample_rate: u32) -> Vec<(f32, usize)> {
+    let frame_samples = ((sample_rate as u64 * SILENCE_FRAME_MS / 1000).max(1)) as usize;
+
+    let decoded = match decoder.decode(packet) {
+        Ok(decoded) => decoded,
+        Err(_) => return Vec::new(),
+    };
+
+    let spec = *decoded.spec();
+    let channels = spec.channels.count().max(1);
+    let mut sample_buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+    sample_buf.copy_interleaved_ref(decoded);
+
+    sample_buf
+        .samples()
+        .chunks(frame_samples * channels)
+        .map(|frame| {
+            let sum_sq: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+            let rms = (sum_sq / frame.len().max(1) as f64).sqrt();
+            let dbfs = if rms > 0.0 {
+                (20.0 * rms.log10()) as f32
+            } else {
+                f32::NEG_INFINITY
+            };
+            (dbfs, frame.len() / channels)
+        })
+        .collect()
+}
+
+/// Tracks runs of quiet audio and records the packet index nearest each run's midpoint
+struct SilenceTracker {
+    threshold_dbfs: f32,
+    min_silence: Duration,
+    in_region: bool,
+    region_start_time: Duration,
+    region_start_packet: usize,
+    region_end_packet: usize,
+    midpoints: Vec<(Duration, usize)>,
+}
+
+impl SilenceTracker {
+    fn new(threshold_dbfs: f32, min_silence: Duration) -> Self {
+        SilenceTracker {
+            threshold_dbfs,
+            min_silence,
+            in_region: false,
+            region_start_time: Duration::from_secs(0),
+            region_start_packet: 0,
+            region_end_packet: 0,
+            midpoints: Vec::new(),
+        }
+    }
+
+    fn observe_frame(&mut self, dbfs: f32, frame_time: Duration, packet_idx: usize) {
+        if dbfs < self.threshold_dbfs {
+            if !self.in_region {
+                self.in_region = true;
+                self.region_start_time = frame_time;
+                self.region_start_packet = packet_idx;
+            }
+            self.region_end_packet = packet_idx;
+        } else {
+            self.close_region(frame_time);
+        }
+    }
+
+    fn close_region(&mut self, end_time: Duration) {
+        if self.in_region {
+            let region_len = end_time.saturating_sub(self.region_start_time);
+            if region_len >= self.min_silence {
+                let midpoint = self.region_start_time + region_len / 2;
+                let packet_idx = (self.region_start_packet + self.region_end_packet) / 2;
+                self.midpoints.push((midpoint, packet_idx));
+            }
+            self.in_region = false;
+        }
+    }
+
+    fn finish(mut self, end_time: Duration) -> Vec<(Duration, usize)> {
+        self.close_region(end_time);
+        self.midpoints
+    }
+}
+
+/// Sample rates (Hz) indexed by the frame header's sample rate field, one table per MPEG version
+const MPEG1_SAMPLE_RATES: [u32; 3] = [44_100, 48_000, 32_000];
+const MPEG2_SAMPLE_RATES: [u32; 3] = [22_050, 24_000, 16_000];
+const MPEG25_SAMPLE_RATES: [u32; 3] = [11_025, 12_000, 8_000];
+
+/// Fields read from the source's first real MPEG frame header, used to
+/// synthesize a matching silent frame for the Xing/Info header
+struct MpegFrameHeader {
+    version_bits: u8,
+    sample_rate: u32,
+    channel_mode_bits: u8,
+    mono: bool,
+    /// `true` for MPEG-2/2.5, which halve the Layer III frame size and side-info length
+    /// relative to MPEG-1
+    is_lsf: bool,
+}
+
+/// Parse the fields needed to synthesize a Xing header frame from a raw MPEG Layer III frame
+///
+/// Handles MPEG-1, MPEG-2, and MPEG-2.5 (the version bits select both the sample rate
+/// table and the frame-size/side-info constants used downstream).
+fn parse_mpeg1_header(data: &[u8]) -> Option<MpegFrameHeader> {
+    if data.len() < 4 || data[0] != 0xFF || (data[1] & 0xE0) != 0xE0 {
+        return None;
+    }
+
+    let version_bits = (data[1] >> 3) & 0x03;
+    let layer_bits = (data[1] >> 1) & 0x03;
+    if layer_bits != 0x01 {
+        return None; // Only Layer III carries a Xing/LAME header
+    }
+    if version_bits == 0b01 {
+        return None; // Reserved version
+    }
+
+    let sample_rate_table = match version_bits {
+        0b11 => &MPEG1_SAMPLE_RATES,  // MPEG-1
+        0b10 => &MPEG2_SAMPLE_RATES,  // MPEG-2
+        _ => &MPEG25_SAMPLE_RATES,    // MPEG-2.5
+    };
+    let sample_rate_index = (data[2] >> 2) & 0x03;
+    let sample_rate = *sample_rate_table.get(sample_rate_index as usize)?;
+
+    let channel_mode_bits = (data[3] >> 6) & 0x03;
+
+    Some(MpegFrameHeader {
+        version_bits,
+        sample_rate,
+        channel_mode_bits,
+        mono: channel_mode_bits == 0x03,
+        is_lsf: version_bits != 0b11,
+    })
+}
+
+/// Encoder delay (priming samples) and padding (trailing samples) to propagate into a
+/// chunk's synthesized Info header so gapless playback survives the split
+#[derive(Clone, Copy, Default)]
+struct GaplessInfo {
+    delay: u16,
+    padding: u16,
+}
+
+/// Parse the encoder delay/padding fields from the source's own LAME tag, if present
+///
+/// The fields live 21 bytes into the LAME extension that follows the standard Xing/Info
+/// block, packed as two 12-bit values.
+fn parse_lame_gapless(first_packet: &[u8], side_info_len: usize) -> Option<GaplessInfo> {
+    let xing_offset = 4 + side_info_len;
+    let tag = first_packet.get(xing_offset..xing_offset + 4)?;
+    if tag != b"Xing" && tag != b"Info" {
+        return None;
+    }
+
+    // tag(4) + flags(4) + frames(4) + bytes(4) + TOC(100) + quality(4) = 116..120
+    let lame_offset = xing_offset + 120;
+    let delay_padding_offset = lame_offset + 21;
+    let bytes = first_packet.get(delay_padding_offset..delay_padding_offset + 3)?;
+
+    let delay = ((bytes[0] as u16) << 4) | ((bytes[1] as u16) >> 4);
+    let padding = (((bytes[1] as u16) & 0x0F) << 8) | (bytes[2] as u16);
+    Some(GaplessInfo { delay, padding })
+}
+
+/// Read the source file's original Xing/Info/LAME header frame to recover its
+/// encoder delay/padding
+///
+/// symphonia's demuxer consumes this frame as part of format detection and never
+/// hands it back as a decodable packet, so `packets[0]` is already a real audio
+/// frame with no Xing tag. The header frame has to be found directly in the raw
+/// file instead, immediately after any leading ID3v2 tag.
+fn read_source_gapless(input_path: &Path) -> Option<GaplessInfo> {
+    let data = fs::read(input_path).ok()?;
+
+    let mut offset = 0usize;
+    if data.len() >= 10 && &data[0..3] == b"ID3" {
+        let size = ((data[6] as u32 & 0x7F) << 21)
+            | ((data[7] as u32 & 0x7F) << 14)
+            | ((data[8] as u32 & 0x7F) << 7)
+            | (data[9] as u32 & 0x7F);
+        offset = 10 + size as usize;
+    }
+
+    while offset + 4 <= data.len() {
+        if data[offset] == 0xFF && (data[offset + 1] & 0xE0) == 0xE0 {
+            let header = parse_mpeg1_header(&data[offset..])?;
+            let side_info_len = match (header.is_lsf, header.mono) {
+                (false, false) => 32,
+                (false, true) => 17,
+                (true, false) => 17,
+                (true, true) => 9,
+            };
+            return parse_lame_gapless(&data[offset..], side_info_len);
+        }
+        offset += 1;
+    }
+
+    None
+}
+
+/// Synthesize a silent MPEG Layer III frame (same version as the source) carrying a
+/// Xing/Info header describing `chunk`
+///
+/// The frame is built large enough (using a fixed 128 kbps bitrate) to hold the side
+/// information plus the Xing payload, with the remainder zero-padded as silence. When
+/// `gapless` is given, a minimal LAME extension carrying delay/padding is appended so
+/// players can trim the encoder's priming and trailing samples.
+fn build_xing_header_frame(
+    source_header: &MpegFrameHeader,
+    packets: &[Packet],
+    chunk: &ChunkInfo,
+    is_vbr: bool,
+    gapless: Option<GaplessInfo>,
+) -> Vec<u8> {
+    // Layer III bitrate index that denotes 128 kbps: index 9 in the MPEG-1 table, but
+    // index 12 in the MPEG-2/2.5 ("LSF") table, where index 9 means 80 kbps. Keeping the
+    // header's declared bitrate and the actual written frame length in agreement matters
+    // because a mismatch leaves a zero gap that breaks frame-by-frame parsing.
+    let bitrate_index: u8 = if source_header.is_lsf { 12 } else { 9 };
+
+    // MPEG-2/2.5 (the "LSF" versions) halve the Layer III frame-size coefficient and
+    // side-info length relative to MPEG-1
+    const BITRATE_BPS: u32 = 128_000;
+    let sample_rate_table: &[u32; 3] = match source_header.version_bits {
+        0b11 => &MPEG1_SAMPLE_RATES,
+        0b10 => &MPEG2_SAMPLE_RATES,
+        _ => &MPEG25_SAMPLE_RATES,
+    };
+    let frame_size_coefficient: u32 = if source_header.is_lsf { 72 } else { 144 };
+
+    let sample_rate_index = sample_rate_table
+        .iter()
+        .position(|&rate| rate == source_header.sample_rate)
+        .unwrap_or(0) as u8;
+
+    let frame_size = (frame_size_coefficient * BITRATE_BPS / source_header.sample_rate) as usize;
+    let side_info_len = match (source_header.is_lsf, source_header.mono) {
+        (false, false) => 32,
+        (false, true) => 17,
+        (true, false) => 17,
+        (true, true) => 9,
+    };
+
+    let mut frame = vec![0u8; frame_size];
+    frame[0] = 0xFF;
+    frame[1] = 0xE0 | (source_header.version_bits << 3) | (0b01 << 1) | 0b1; // Layer III, no CRC
+    frame[2] = (bitrate_index << 4) | (sample_rate_index << 2);
+    frame[3] = source_header.channel_mode_bits << 6;
+
+    let packet_lens: Vec<u32> = chunk.packets.iter().map(|&i| packets[i].data.len() as u32).collect();
+    let payload_bytes: u32 = packet_lens.iter().sum();
+    let frame_count = chunk.packets.len() as u32;
+    let byte_count = payload_bytes + frame_size as u32;
+
+    let mut toc = [0u8; 100];
+    if payload_bytes > 0 && !packet_lens.is_empty() {
+        let mut cumulative = 0u32;
+        let mut packet_cursor = 0usize;
+        for (percent, entry) in toc.iter_mut().enumerate() {
+            let target_packet = (percent * packet_lens.len()) / 100;
+            while packet_cursor < target_packet {
+                cumulative += packet_lens[packet_cursor];
+                packet_cursor += 1;
+            }
+            *entry = ((cumulative as u64 * 255) / payload_bytes as u64) as u8;
+        }
+    }
+
+    let tag: &[u8; 4] = if is_vbr { b"Xing" } else { b"Info" };
+    let mut cursor = 4 + side_info_len;
+    frame[cursor..cursor + 4].copy_from_slice(tag);
+    cursor += 4;
+    frame[cursor..cursor + 4].copy_from_slice(&0x0Fu32.to_be_bytes());
+    cursor += 4;
+    frame[cursor..cursor + 4].copy_from_slice(&frame_count.to_be_bytes());
+    cursor += 4;
+    frame[cursor..cursor + 4].copy_from_slice(&byte_count.to_be_bytes());
+    cursor += 4;
+    frame[cursor..cursor + 100].copy_from_slice(&toc);
+    cursor += 100;
+    frame[cursor..cursor + 4].copy_from_slice(&0u32.to_be_bytes()); // quality
+    cursor += 4;
+
+    if let Some(gapless) = gapless {
+        frame[cursor..cursor + 9].copy_from_slice(b"LAME3.100");
+        cursor += 9 + 12; // encoder version, then revision/vbr/lowpass/replaygain/ATH/bitrate fields left zeroed
+        let delay_padding = [
+            (gapless.delay >> 4) as u8,
+            (((gapless.delay & 0x0F) << 4) | ((gapless.padding >> 8) & 0x0F)) as u8,
+            (gapless.padding & 0xFF) as u8,
+        ];
+        frame[cursor..cursor + 3].copy_from_slice(&delay_padding);
+    }
+
+    frame
+}
+
+/// Returns `true` when the input should be split into self-contained MP4/M4A chunks
+/// instead of raw MP3 frame copies
+fn is_mp4_aac(input_path: &Path, codec_params: &CodecParameters) -> bool {
+    let has_mp4_extension = input_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("m4a") || ext.eq_ignore_ascii_case("mp4"))
+        .unwrap_or(false);
+
+    has_mp4_extension && codec_params.codec == CODEC_TYPE_AAC
+}
+
+/// Write an ISO-BMFF box: a big-endian `size` + 4-byte `box_type` header followed by `content`
+fn write_box(buf: &mut Vec<u8>, box_type: &[u8; 4], content: &[u8]) {
+    buf.extend_from_slice(&((8 + content.len()) as u32).to_be_bytes());
+    buf.extend_from_slice(box_type);
+    buf.extend_from_slice(content);
+}
+
+/// The 3x3 identity transformation matrix used by `tkhd`/`mvhd` boxes (16.16 fixed point,
+/// except the last column, which is 2.30)
+const UNITY_MATRIX: [u8; 36] = [
+    0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00,
+];
+
+/// Build the `ftyp` box declaring this file as a self-contained M4A
+fn build_ftyp_box() -> Vec<u8> {
+    let mut content = Vec::new();
+    content.extend_from_slice(b"M4A "); // major_brand
+    content.extend_from_slice(&0u32.to_be_bytes()); // minor_version
+    content.extend_from_slice(b"M4A "); // compatible_brands
+    content.extend_from_slice(b"mp42");
+    content.extend_from_slice(b"isom");
+
+    let mut buf = Vec::new();
+    write_box(&mut buf, b"ftyp", &content);
+    buf
+}
+
+/// Build the sample tables (`stbl`) describing one chunk's packets
+fn build_stbl_box(codec_params: &CodecParameters, packets: &[Packet], chunk: &ChunkInfo, mdat_payload_offset: u32) -> Vec<u8> {
+    let channels = codec_params.channels.map(|c| c.count()).unwrap_or(2) as u16;
+    let sample_rate = codec_params.sample_rate.unwrap_or(44_100);
+    let extra_data = codec_params.extra_data.clone().unwrap_or_default();
+
+    // stsd: sample description, one mp4a entry carrying an esds with the AAC decoder config
+    let mut esds_content = Vec::new();
+    esds_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    let decoder_specific_len = extra_data.len();
+    let decoder_config_len = 13 + 2 + decoder_specific_len; // fields below + DecoderSpecificInfo tag/size/data
+    let es_descriptor_len = 3 + 2 + decoder_config_len + 2 + 1; // ES_ID/flags + DecoderConfigDescriptor + SLConfigDescriptor
+    esds_content.push(0x03); // ES_DescriptorTag
+    esds_content.push(es_descriptor_len as u8);
+    esds_content.extend_from_slice(&1u16.to_be_bytes()); // ES_ID
+    esds_content.push(0x00); // flags
+    esds_content.push(0x04); // DecoderConfigDescrTag
+    esds_content.push(decoder_config_len as u8);
+    esds_content.push(0x40); // objectTypeIndication: MPEG-4 AAC
+    esds_content.push(0x15); // streamType (audio) << 2 | upStream << 1 | reserved
+    esds_content.extend_from_slice(&[0x00, 0x00, 0x00]); // bufferSizeDB
+    esds_content.extend_from_slice(&0u32.to_be_bytes()); // maxBitrate
+    esds_content.extend_from_slice(&0u32.to_be_bytes()); // avgBitrate
+    esds_content.push(0x05); // DecoderSpecificInfoTag
+    esds_content.push(decoder_specific_len as u8);
+    esds_content.extend_from_slice(&extra_data);
+    esds_content.push(0x06); // SLConfigDescrTag
+    esds_content.push(0x01);
+    esds_content.push(0x02); // MP4 file
+
+    let mut mp4a_content = Vec::new();
+    mp4a_content.extend_from_slice(&[0u8; 6]); // reserved
+    mp4a_content.extend_from_slice(&1u16.to_be_bytes()); // data_reference_index
+    mp4a_content.extend_from_slice(&[0u8; 8]); // version/revision/vendor
+    mp4a_content.extend_from_slice(&channels.to_be_bytes());
+    mp4a_content.extend_from_slice(&16u16.to_be_bytes()); // sample_size
+    mp4a_content.extend_from_slice(&[0u8; 4]); // pre_defined/reserved
+    // 16.16 fixed point; sample rates above 65535 Hz do not fit the 16-bit integer
+    // part, and their high bits are dropped by the cast back to u32
+    let sample_rate_fixed = ((sample_rate as u64) << 16) as u32;
+    mp4a_content.extend_from_slice(&sample_rate_fixed.to_be_bytes());
+    write_box(&mut mp4a_content, b"esds", &esds_content);
+
+    let mut stsd_content = Vec::new();
+    stsd_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    stsd_content.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+    write_box(&mut stsd_content, b"mp4a", &mp4a_content);
+
+    // stts: run-length encode each packet's sample duration
+    let mut stts_entries = Vec::new();
+    for &packet_idx in &chunk.packets {
+        let duration = packets[packet_idx].dur as u32;
+        if let Some((count, last_duration)) = stts_entries.last_mut() {
+            if *last_duration == duration {
+                *count += 1u32;
+                continue;
+            }
+        }
+        stts_entries.push((1u32, duration));
+    }
+    let mut stts_content = Vec::new();
+    stts_content.extend_from_slice(&0u32.to_be_bytes());
+    stts_content.extend_from_slice(&(stts_entries.len() as u32).to_be_bytes());
+    for (count, duration) in &stts_entries {
+        stts_content.extend_from_slice(&count.to_be_bytes());
+        stts_content.extend_from_slice(&duration.to_be_bytes());
+    }
+
+    // stsz: one entry per packet, using its exact byte length
+    let mut stsz_content = Vec::new();
+    stsz_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    stsz_content.extend_from_slice(&0u32.to_be_bytes()); // sample_size (0 = explicit sizes follow)
+    stsz_content.extend_from_slice(&(chunk.packets.len() as u32).to_be_bytes());
+    for &packet_idx in &chunk.packets {
+        stsz_content.extend_from_slice(&(packets[packet_idx].data.len() as u32).to_be_bytes());
+    }
+
+    // stsc: every sample belongs to the single mdat "chunk"
+    let mut stsc_content = Vec::new();
+    stsc_content.extend_from_slice(&0u32.to_be_bytes());
+    stsc_content.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+    stsc_content.extend_from_slice(&1u32.to_be_bytes()); // first_chunk
+    stsc_content.extend_from_slice(&(chunk.packets.len() as u32).to_be_bytes()); // samples_per_chunk
+    stsc_content.extend_from_slice(&1u32.to_be_bytes()); // sample_description_index
+
+    // stco: a single chunk offset pointing at the start of the mdat payload
+    let mut stco_content = Vec::new();
+    stco_content.extend_from_slice(&0u32.to_be_bytes());
+    stco_content.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+    stco_content.extend_from_slice(&mdat_payload_offset.to_be_bytes());
+
+    let mut stbl_content = Vec::new();
+    write_box(&mut stbl_content, b"stsd", &stsd_content);
+    write_box(&mut stbl_content, b"stts", &stts_content);
+    write_box(&mut stbl_content, b"stsc", &stsc_content);
+    write_box(&mut stbl_content, b"stsz", &stsz_content);
+    write_box(&mut stbl_content, b"stco", &stco_content);
+
+    let mut buf = Vec::new();
+    write_box(&mut buf, b"stbl", &stbl_content);
+    buf
+}
+
+/// Build an `edts`/`elst` box trimming `delay_samples` of priming from the start and
+/// `padding_samples` of trailing samples from the end of a chunk's media
+fn build_edts_box(delay_samples: u32, padding_samples: u32, chunk_samples: u32) -> Vec<u8> {
+    let visible_samples = chunk_samples.saturating_sub(delay_samples + padding_samples);
+
+    let mut elst_content = Vec::new();
+    elst_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    elst_content.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+    elst_content.extend_from_slice(&visible_samples.to_be_bytes()); // segment_duration
+    elst_content.extend_from_slice(&(delay_samples as i32).to_be_bytes()); // media_time
+    elst_content.extend_from_slice(&1u16.to_be_bytes()); // media_rate_integer
+    elst_content.extend_from_slice(&0u16.to_be_bytes()); // media_rate_fraction
+
+    let mut elst_box = Vec::new();
+    write_box(&mut elst_box, b"elst", &elst_content);
+
+    let mut edts_box = Vec::new();
+    write_box(&mut edts_box, b"edts", &elst_box);
+    edts_box
+}
+
+/// Build the `moov` box for one chunk, given where its `mdat` payload will start in the file
+fn build_moov_box(
+    codec_params: &CodecParameters,
+    packets: &[Packet],
+    chunk: &ChunkInfo,
+    mdat_payload_offset: u32,
+    gapless: Option<GaplessInfo>,
+) -> Vec<u8> {
+    let sample_rate = codec_params.sample_rate.unwrap_or(44_100);
+    let chunk_samples: u32 = chunk.packets.iter().map(|&i| packets[i].dur as u32).sum();
+
+    // mvhd
+    let mut mvhd_content = Vec::new();
+    mvhd_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    mvhd_content.extend_from_slice(&0u32.to_be_bytes()); // creation_time
+    mvhd_content.extend_from_slice(&0u32.to_be_bytes()); // modification_time
+    mvhd_content.extend_from_slice(&sample_rate.to_be_bytes()); // timescale
+    mvhd_content.extend_from_slice(&chunk_samples.to_be_bytes()); // duration
+    mvhd_content.extend_from_slice(&0x0001_0000u32.to_be_bytes()); // rate
+    mvhd_content.extend_from_slice(&0x0100u16.to_be_bytes()); // volume
+    mvhd_content.extend_from_slice(&[0u8; 10]); // reserved
+    mvhd_content.extend_from_slice(&UNITY_MATRIX);
+    mvhd_content.extend_from_slice(&[0u8; 24]); // pre_defined
+    mvhd_content.extend_from_slice(&2u32.to_be_bytes()); // next_track_ID
+
+    // tkhd
+    let mut tkhd_content = Vec::new();
+    tkhd_content.extend_from_slice(&1u32.to_be_bytes()); // version/flags: track enabled
+    tkhd_content.extend_from_slice(&0u32.to_be_bytes()); // creation_time
+    tkhd_content.extend_from_slice(&0u32.to_be_bytes()); // modification_time
+    tkhd_content.extend_from_slice(&1u32.to_be_bytes()); // track_ID
+    tkhd_content.extend_from_slice(&0u32.to_be_bytes()); // reserved
+    tkhd_content.extend_from_slice(&chunk_samples.to_be_bytes()); // duration
+    tkhd_content.extend_from_slice(&[0u8; 8]); // reserved
+    tkhd_content.extend_from_slice(&0u16.to_be_bytes()); // layer
+    tkhd_content.extend_from_slice(&0u16.to_be_bytes()); // alternate_group
+    tkhd_content.extend_from_slice(&0x0100u16.to_be_bytes()); // volume
+    tkhd_content.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    tkhd_content.extend_from_slice(&UNITY_MATRIX);
+    tkhd_content.extend_from_slice(&0u32.to_be_bytes()); // width
+    tkhd_content.extend_from_slice(&0u32.to_be_bytes()); // height
+
+    // mdhd
+    let mut mdhd_content = Vec::new();
+    mdhd_content.extend_from_slice(&0u32.to_be_bytes());
+    mdhd_content.extend_from_slice(&0u32.to_be_bytes()); // creation_time
+    mdhd_content.extend_from_slice(&0u32.to_be_bytes()); // modification_time
+    mdhd_content.extend_from_slice(&sample_rate.to_be_bytes()); // timescale
+    mdhd_content.extend_from_slice(&chunk_samples.to_be_bytes()); // duration
+    mdhd_content.extend_from_slice(&0x55C4u16.to_be_bytes()); // language: und
+    mdhd_content.extend_from_slice(&0u16.to_be_bytes()); // pre_defined
+
+    // hdlr
+    let mut hdlr_content = Vec::new();
+    hdlr_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    hdlr_content.extend_from_slice(&0u32.to_be_bytes()); // pre_defined
+    hdlr_content.extend_from_slice(b"soun"); // handler_type
+    hdlr_content.extend_from_slice(&[0u8; 12]); // reserved
+    hdlr_content.extend_from_slice(b"SoundHandler\0");
+
+    // smhd
+    let mut smhd_content = Vec::new();
+    smhd_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    smhd_content.extend_from_slice(&0u16.to_be_bytes()); // balance
+    smhd_content.extend_from_slice(&0u16.to_be_bytes()); // reserved
+
+    // dinf: a single self-contained data reference
+    let mut url_box = Vec::new();
+    write_box(&mut url_box, b"url ", &1u32.to_be_bytes()); // flags = 1: media in this file
+    let mut dref_content = Vec::new();
+    dref_content.extend_from_slice(&0u32.to_be_bytes()); // version/flags
+    dref_content.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+    dref_content.extend_from_slice(&url_box);
+    let mut dinf_content = Vec::new();
+    write_box(&mut dinf_content, b"dref", &dref_content);
+
+    let stbl_box = build_stbl_box(codec_params, packets, chunk, mdat_payload_offset);
+
+    let mut minf_content = Vec::new();
+    write_box(&mut minf_content, b"smhd", &smhd_content);
+    write_box(&mut minf_content, b"dinf", &dinf_content);
+    minf_content.extend_from_slice(&stbl_box);
+
+    let mut mdia_content = Vec::new();
+    write_box(&mut mdia_content, b"mdhd", &mdhd_content);
+    write_box(&mut mdia_content, b"hdlr", &hdlr_content);
+    write_box(&mut mdia_content, b"minf", &minf_content);
+
+    let mut trak_content = Vec::new();
+    write_box(&mut trak_content, b"tkhd", &tkhd_content);
+    if let Some(gapless) = gapless {
+        trak_content.extend_from_slice(&build_edts_box(gapless.delay as u32, gapless.padding as u32, chunk_samples));
+    }
+    write_box(&mut trak_content, b"mdia", &mdia_content);
+
+    let mut moov_content = Vec::new();
+    write_box(&mut moov_content, b"mvhd", &mvhd_content);
+    write_box(&mut moov_content, b"trak", &trak_content);
+
+    let mut buf = Vec::new();
+    write_box(&mut buf, b"moov", &moov_content);
+    buf
+}
+
+/// Write one chunk as a minimal, self-contained MP4/M4A file: `ftyp` + `moov` + `mdat`
+fn write_mp4_chunk(
+    options: &SplitOptions,
+    output_path: &Path,
+    codec_params: &CodecParameters,
+    packets: &[Packet],
+    chunk: &ChunkInfo,
+    is_first_chunk: bool,
+    is_last_chunk: bool,
+) -> io::Result<()> {
+    let ftyp_box = build_ftyp_box();
+
+    // Source-level encoder delay/padding only apply at the very start/end of the
+    // original stream; interior chunk boundaries carry no priming or padding
+    let gapless = options.gapless.then(|| GaplessInfo {
+        delay: if is_first_chunk { codec_params.delay.unwrap_or(0) as u16 } else { 0 },
+        padding: if is_last_chunk { codec_params.padding.unwrap_or(0) as u16 } else { 0 },
+    });
+
+    // moov's size doesn't depend on the mdat offset value, only its presence, so a
+    // dummy pass gives us the exact length needed to compute the real offset
+    let moov_len_probe = build_moov_box(codec_params, packets, chunk, 0, gapless).len();
+    let mdat_payload_offset = (ftyp_box.len() + moov_len_probe + 8) as u32;
+    let moov_box = build_moov_box(codec_params, packets, chunk, mdat_payload_offset, gapless);
+
+    let mut output = BufWriter::new(File::create(output_path)?);
+    output.write_all(&ftyp_box)?;
+    output.write_all(&moov_box)?;
+
+    let mdat_payload_len: usize = chunk.packets.iter().map(|&i| packets[i].data.len()).sum();
+    output.write_all(&((8 + mdat_payload_len) as u32).to_be_bytes())?;
+    output.write_all(b"mdat")?;
+    for &packet_idx in &chunk.packets {
+        output.write_all(&packets[packet_idx].data)?;
+    }
+
+    output.flush()
+}
+
+/// Write a single chunk's output file, including its synthesized header and ID3 tag
+/// (MP3) or container boxes (MP4; no ID3 tag is written)
+///
+/// Returns the output path plus any non-fatal ID3 tag-write warning, rather than
+/// printing it directly, so callers writing chunks in parallel still see it.
+#[allow(clippy::too_many_arguments)]
+fn write_chunk_output(
+    options: &SplitOptions,
+    codec_params: &CodecParameters,
+    packets: &[Packet],
+    original_tag: &Option<Tag>,
+    source_header: Option<&MpegFrameHeader>,
+    source_gapless: Option<GaplessInfo>,
+    is_vbr: bool,
+    write_as_mp4: bool,
+    output_extension: &str,
+    chunk_idx: usize,
+    chunk_count: usize,
+    chunk: &ChunkInfo,
+) -> io::Result<(PathBuf, Option<String>)> {
+    let output_filename = format!("{}_{:03}.{}", options.prefix, chunk_idx + 1, output_extension);
+    let output_path = options.output_dir.join(&output_filename);
+    let is_first_chunk = chunk_idx == 0;
+    let is_last_chunk = chunk_idx == chunk_count - 1;
+
+    println!(
+        "Writing chunk {}/{}: {} (duration: {:.2} minutes, {} packets)",
+        chunk_idx + 1,
+        chunk_count,
+        output_filename,
+        (chunk.end_time - chunk.start_time).as_secs_f64() / 60.0,
+        chunk.packets.len()
+    );
+
+    if write_as_mp4 {
+        write_mp4_chunk(options, &output_path, codec_params, packets, chunk, is_first_chunk, is_last_chunk)?;
+        return Ok((output_path, None));
+    }
+
+    let mut output = BufWriter::new(File::create(&output_path)?);
+
+    // Prepend a synthesized Xing/Info header frame so players compute duration
+    // and seek positions correctly for this (possibly VBR) chunk
+    if let Some(header) = source_header {
+        // Delay only belongs on the first chunk and padding only on the last, so
+        // a gapless-reassembled stream doesn't gain silence at interior boundaries
+        let chunk_gapless = if options.gapless {
+            source_gapless.map(|g| GaplessInfo {
+                delay: if is_first_chunk { g.delay } else { 0 },
+                padding: if is_last_chunk { g.padding } else { 0 },
+            })
+        } else {
+            None
+        };
+        let header_frame = build_xing_header_frame(header, packets, chunk, is_vbr, chunk_gapless);
+        output.write_all(&header_frame)?;
+    }
+
+    // Write all packets for this chunk
+    for &packet_idx in &chunk.packets {
+        output.write_all(&packets[packet_idx].data)?;
+    }
+    output.flush()?;
+
+    // Apply ID3 tags with modifications
+    let mut warning = None;
+    if let Some(tag) = original_tag {
+        let mut new_tag = tag.clone();
+
+        // Update the title to include part number
+        if let Some(title) = new_tag.title() {
+            let new_title = format!("{} (Part {}/{})", title, chunk_idx + 1, chunk_count);
+            new_tag.set_title(new_title);
+        }
+
+        // Set track number
+        new_tag.set_track((chunk_idx + 1) as u32);
+
+        // Write the tag to the new file
+        if let Err(e) = new_tag.write_to_path(&output_path, Version::Id3v24) {
+            warning = Some(format!("Failed to write ID3 tags for {}: {}", output_filename, e));
+        }
+    }
+
+    Ok((output_path, warning))
 }
 
 /// Split an MP3 file into chunks of specified duration
@@ -50,7 +794,7 @@ pub struct SplitResult {
 ///
 /// # Example
 /// ```no_run
-/// use mp3_splitter::{SplitOptions, split_mp3};
+/// use mp3_splitter::{SplitMode, SplitOptions, split_mp3};
 /// use std::path::Path;
 /// use std::time::Duration;
 ///
@@ -59,6 +803,11 @@ pub struct SplitResult {
 ///     chunk_duration: Duration::from_secs(600), // 10 minutes
 ///     output_dir: Path::new("chunks"),
 ///     prefix: "track",
+///     emit_hls: false,
+///     hls_target_duration: None,
+///     split_mode: SplitMode::FixedDuration,
+///     gapless: false,
+///     parallel: false,
 /// };
 ///
 /// match split_mp3(&options) {
@@ -83,7 +832,9 @@ pub fn split_mp3(options: &SplitOptions) -> io::Result<SplitResult> {
     
     // Create a hint to help with format detection
     let mut hint = Hint::new();
-    hint.with_extension("mp3");
+    if let Some(extension) = options.input_path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
     
     // Use default options
     let format_opts = FormatOptions::default();
@@ -92,18 +843,18 @@ pub fn split_mp3(options: &SplitOptions) -> io::Result<SplitResult> {
     // Probe the format
     let probed = symphonia::default::get_probe()
         .format(&hint, mss, &format_opts, &metadata_opts)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error probing format: {}", e)))?;
+        .map_err(|e| io::Error::other(format!("Error probing format: {}", e)))?;
     
     let mut format = probed.format;
     
     // Get the default track
     let track = format.default_track()
-        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No default track found"))?;
+        .ok_or_else(|| io::Error::other("No default track found"))?;
     
     // Get codec parameters and time base
     let codec_params = track.codec_params.clone();
     let time_base = codec_params.time_base
-        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No time base found"))?;
+        .ok_or_else(|| io::Error::other("No time base found"))?;
     
     // Read original ID3 tags
     let original_tag = Tag::read_from_path(options.input_path).ok();
@@ -112,7 +863,25 @@ pub fn split_mp3(options: &SplitOptions) -> io::Result<SplitResult> {
     let mut packets = Vec::new();
     let mut packet_times = Vec::new();
     let mut total_duration = Duration::from_secs(0);
-    
+
+    // Set up silence detection when the caller asked for silence-aligned boundaries
+    let mut silence_decoder = match options.split_mode {
+        SplitMode::SilenceAligned { .. } => Some(
+            symphonia::default::get_codecs()
+                .make(&codec_params, &DecoderOptions::default())
+                .map_err(|e| io::Error::other(format!("Error creating decoder: {}", e)))?,
+        ),
+        SplitMode::FixedDuration => None,
+    };
+    let mut silence_tracker = match options.split_mode {
+        SplitMode::SilenceAligned { threshold_dbfs, min_silence, .. } => {
+            Some(SilenceTracker::new(threshold_dbfs, min_silence))
+        }
+        SplitMode::FixedDuration => None,
+    };
+    let sample_rate = codec_params.sample_rate.unwrap_or(44_100);
+    let mut frame_time = Duration::from_secs(0);
+
     // First pass: read all packets and calculate timestamps
     println!("First pass: reading packets and calculating timestamps...");
     while let Ok(packet) = format.next_packet() {
@@ -121,15 +890,29 @@ pub fn split_mp3(options: &SplitOptions) -> io::Result<SplitResult> {
         let packet_duration = Duration::from_secs_f64(
             frame_len as f64 * time_base.numer as f64 / time_base.denom as f64
         );
-        
+
+        if let (Some(decoder), Some(tracker)) = (silence_decoder.as_mut(), silence_tracker.as_mut()) {
+            // Advance by each frame's actual sample count, not a flat 20ms tick, so
+            // `frame_time` stays in lockstep with `packet_times` even when a packet's
+            // sample count doesn't divide evenly into analysis frames
+            for (dbfs, frame_samples) in packet_frame_levels(decoder.as_mut(), &packet, sample_rate) {
+                tracker.observe_frame(dbfs, frame_time, packets.len());
+                frame_time += Duration::from_secs_f64(frame_samples as f64 / sample_rate as f64);
+            }
+        }
+
         total_duration += packet_duration;
         packet_times.push(total_duration);
         packets.push(packet);
     }
-    
+
     if packets.is_empty() {
-        return Err(io::Error::new(io::ErrorKind::Other, "No audio packets found"));
+        return Err(io::Error::other("No audio packets found"));
     }
+
+    let silence_midpoints = silence_tracker
+        .map(|tracker| tracker.finish(total_duration))
+        .unwrap_or_default();
     
     println!("Found {} packets, total duration: {:.2} seconds ({:.2} minutes)", 
         packets.len(), 
@@ -149,12 +932,29 @@ pub fn split_mp3(options: &SplitOptions) -> io::Result<SplitResult> {
         
         // Find the packet index that's closest to our target end time
         let mut chunk_end_packet = chunk_start_packet;
-        while chunk_end_packet < packets.len() && 
-              (chunk_end_packet == chunk_start_packet || 
+        while chunk_end_packet < packets.len() &&
+              (chunk_end_packet == chunk_start_packet ||
                packet_times[chunk_end_packet - 1] < target_end_time) {
             chunk_end_packet += 1;
         }
-        
+
+        // In silence-aligned mode, snap to the nearest quiet gap instead of cutting mid-sentence
+        if let SplitMode::SilenceAligned { search_window, .. } = options.split_mode {
+            let nearest = silence_midpoints
+                .iter()
+                .filter_map(|&(midpoint, packet_idx)| {
+                    let distance = midpoint.abs_diff(target_end_time);
+                    (distance <= search_window).then_some((distance, packet_idx))
+                })
+                .min_by_key(|&(distance, _)| distance);
+
+            if let Some((_, packet_idx)) = nearest {
+                if packet_idx > chunk_start_packet && packet_idx <= packets.len() {
+                    chunk_end_packet = packet_idx;
+                }
+            }
+        }
+
         // Ensure we include at least one packet
         if chunk_end_packet == chunk_start_packet {
             chunk_end_packet = chunk_start_packet + 1;
@@ -198,63 +998,281 @@ pub fn split_mp3(options: &SplitOptions) -> io::Result<SplitResult> {
             i+1, duration/60.0, duration, chunk.packets.len());
     }
     
-    // Store output file paths
+    // Inspect the source's first frame to synthesize a matching Xing/Info header per chunk
+    let source_header = parse_mpeg1_header(&packets[0].data);
+    let is_vbr = packets.windows(2).any(|pair| pair[0].data.len() != pair[1].data.len());
+    // The original Xing/LAME header frame isn't among the demuxed packets, so its
+    // delay/padding have to be read straight from the source file
+    let source_gapless = read_source_gapless(options.input_path);
+
+    // Containers other than raw MP3 (currently MP4/M4A carrying AAC) need their own
+    // self-contained output format rather than a packet byte-copy
+    let write_as_mp4 = is_mp4_aac(options.input_path, &codec_params);
+    let output_extension = if write_as_mp4 { "m4a" } else { "mp3" };
+
+    // Third pass: write chunks to files. Each chunk owns a disjoint slice of `packets`,
+    // so this can run on a rayon thread pool when `options.parallel` is set.
+    let chunk_results: Vec<io::Result<(PathBuf, Option<String>)>> = if options.parallel {
+        chunks
+            .par_iter()
+            .enumerate()
+            .map(|(chunk_idx, chunk)| {
+                write_chunk_output(
+                    options, &codec_params, &packets, &original_tag, source_header.as_ref(),
+                    source_gapless, is_vbr, write_as_mp4, output_extension, chunk_idx, chunks.len(), chunk,
+                )
+            })
+            .collect()
+    } else {
+        chunks
+            .iter()
+            .enumerate()
+            .map(|(chunk_idx, chunk)| {
+                write_chunk_output(
+                    options, &codec_params, &packets, &original_tag, source_header.as_ref(),
+                    source_gapless, is_vbr, write_as_mp4, output_extension, chunk_idx, chunks.len(), chunk,
+                )
+            })
+            .collect()
+    };
+
     let mut output_files = Vec::with_capacity(chunks.len());
-    
-    // Third pass: write chunks to files
-    for (chunk_idx, chunk) in chunks.iter().enumerate() {
-        let output_filename = format!("{}_{:03}.mp3", options.prefix, chunk_idx + 1);
-        let output_path = options.output_dir.join(&output_filename);
-        output_files.push(output_path.clone());
-        
-        println!(
-            "Writing chunk {}/{}: {} (duration: {:.2} minutes, {} packets)",
-            chunk_idx + 1,
-            chunks.len(),
-            output_filename,
-            (chunk.end_time - chunk.start_time).as_secs_f64() / 60.0,
-            chunk.packets.len()
-        );
-        
-        let mut output = BufWriter::new(File::create(&output_path)?);
-        
-        // Write all packets for this chunk
-        for &packet_idx in &chunk.packets {
-            output.write_all(&packets[packet_idx].data)?;
-        }
-        output.flush()?;
-        
-        // Apply ID3 tags with modifications
-        if let Some(ref tag) = original_tag {
-            let mut new_tag = tag.clone();
-            
-            // Update the title to include part number
-            if let Some(title) = new_tag.title() {
-                let new_title = format!("{} (Part {}/{})", title, chunk_idx + 1, chunks.len());
-                new_tag.set_title(new_title);
-            }
-            
-            // Set track number
-            new_tag.set_track((chunk_idx + 1) as u32);
-            
-            // Write the tag to the new file
-            if let Err(e) = new_tag.write_to_path(&output_path, Version::Id3v24) {
-                eprintln!("Warning: Failed to write ID3 tags: {}", e);
-            }
+    let mut warnings = Vec::new();
+    for result in chunk_results {
+        let (output_path, warning) = result?;
+        output_files.push(output_path);
+        if let Some(warning) = warning {
+            warnings.push(warning);
         }
     }
-    
-    println!("Successfully split MP3 file into {} chunks in directory: {}", 
+
+    println!("Successfully split MP3 file into {} chunks in directory: {}",
         chunks.len(), options.output_dir.display());
-    
+
+    if options.emit_hls {
+        write_hls_playlist(options, &chunks, &output_files)?;
+    }
+
     Ok(SplitResult {
         chunk_count: chunks.len(),
         total_duration,
         output_files,
+        warnings,
     })
 }
 
+/// Write an `index.m3u8` VOD media playlist referencing every output chunk
+fn write_hls_playlist(
+    options: &SplitOptions,
+    chunks: &[ChunkInfo],
+    output_files: &[PathBuf],
+) -> io::Result<()> {
+    let target_duration = options.hls_target_duration.unwrap_or_else(|| {
+        chunks
+            .iter()
+            .map(|chunk| (chunk.end_time - chunk.start_time).as_secs_f64().ceil() as u32)
+            .max()
+            .unwrap_or(0)
+    });
+
+    let playlist_path = options.output_dir.join("index.m3u8");
+    let mut playlist = BufWriter::new(File::create(&playlist_path)?);
+
+    writeln!(playlist, "#EXTM3U")?;
+    writeln!(playlist, "#EXT-X-VERSION:3")?;
+    writeln!(playlist, "#EXT-X-TARGETDURATION:{}", target_duration)?;
+    writeln!(playlist, "#EXT-X-MEDIA-SEQUENCE:0")?;
+    writeln!(playlist, "#EXT-X-PLAYLIST-TYPE:VOD")?;
+
+    for (chunk, output_path) in chunks.iter().zip(output_files) {
+        let duration = (chunk.end_time - chunk.start_time).as_secs_f64();
+        let file_name = output_path
+            .file_name()
+            .ok_or_else(|| io::Error::other("Chunk path has no file name"))?
+            .to_string_lossy();
+        writeln!(playlist, "#EXTINF:{},", duration)?;
+        writeln!(playlist, "{}", file_name)?;
+    }
+
+    writeln!(playlist, "#EXT-X-ENDLIST")?;
+    playlist.flush()?;
+
+    println!("Wrote HLS playlist: {}", playlist_path.display());
+
+    Ok(())
+}
+
 /// Utility function to convert minutes to Duration
 pub fn minutes_to_duration(minutes: u64) -> Duration {
     Duration::from_secs(minutes * 60)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mpeg1_header() -> MpegFrameHeader {
+        MpegFrameHeader {
+            version_bits: 0b11,
+            sample_rate: 44_100,
+            channel_mode_bits: 0b11,
+            mono: true,
+            is_lsf: false,
+        }
+    }
+
+    fn chunk_with_packets(packet_lens: &[usize]) -> (Vec<Packet>, ChunkInfo) {
+        let packets: Vec<Packet> = packet_lens
+            .iter()
+            .enumerate()
+            .map(|(i, &len)| Packet::new_from_slice(0, i as u64, 1152, &vec![0u8; len]))
+            .collect();
+        let chunk = ChunkInfo {
+            start_time: Duration::from_secs(0),
+            end_time: Duration::from_secs(1),
+            packets: (0..packets.len()).collect(),
+        };
+        (packets, chunk)
+    }
+
+    #[test]
+    fn xing_header_round_trips_frame_and_byte_counts() {
+        let header = mpeg1_header();
+        let (packets, chunk) = chunk_with_packets(&[200, 210, 190]);
+
+        let frame = build_xing_header_frame(&header, &packets, &chunk, true, None);
+
+        // Mono MPEG-1 side info is 17 bytes, so the Xing tag sits right after it
+        let tag = &frame[4 + 17..4 + 17 + 4];
+        assert_eq!(tag, b"Xing");
+
+        let frame_count = u32::from_be_bytes(frame[29..33].try_into().unwrap());
+        assert_eq!(frame_count, 3);
+
+        let byte_count = u32::from_be_bytes(frame[33..37].try_into().unwrap());
+        let payload_bytes: u32 = packets.iter().map(|p| p.data.len() as u32).sum();
+        assert_eq!(byte_count, payload_bytes + frame.len() as u32);
+    }
+
+    #[test]
+    fn xing_header_round_trips_for_mpeg2_source() {
+        let header = MpegFrameHeader {
+            version_bits: 0b10, // MPEG-2
+            sample_rate: 22_050,
+            channel_mode_bits: 0b11,
+            mono: true,
+            is_lsf: true,
+        };
+        let (packets, chunk) = chunk_with_packets(&[100, 100]);
+
+        let frame = build_xing_header_frame(&header, &packets, &chunk, true, None);
+
+        // MPEG-2 mono side info is 9 bytes
+        let tag = &frame[4 + 9..4 + 9 + 4];
+        assert_eq!(tag, b"Xing");
+
+        // The declared bitrate index (upper nibble of byte 2) must denote the same
+        // 128 kbps the frame was actually sized for, or the zero-padded tail beyond
+        // the declared frame length breaks frame-by-frame parsing
+        const MPEG2_LAYER3_BITRATES_KBPS: [u32; 16] = [
+            0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160, 0,
+        ];
+        let bitrate_index = (frame[2] >> 4) as usize;
+        let declared_bitrate_bps = MPEG2_LAYER3_BITRATES_KBPS[bitrate_index] * 1000;
+        let expected_len = (72 * declared_bitrate_bps / header.sample_rate) as usize;
+        assert_eq!(frame.len(), expected_len);
+    }
+
+    #[test]
+    fn gapless_delay_and_padding_round_trip_through_xing_header() {
+        let header = mpeg1_header();
+        let (packets, chunk) = chunk_with_packets(&[200]);
+        let gapless = GaplessInfo { delay: 576, padding: 1151 };
+
+        let frame = build_xing_header_frame(&header, &packets, &chunk, true, Some(gapless));
+
+        let recovered = parse_lame_gapless(&frame, 17).expect("LAME tag should be present");
+        assert_eq!(recovered.delay, gapless.delay);
+        assert_eq!(recovered.padding, gapless.padding);
+    }
+
+    #[test]
+    fn parse_lame_gapless_returns_none_without_xing_tag() {
+        let frame = vec![0u8; 200];
+        assert!(parse_lame_gapless(&frame, 17).is_none());
+    }
+
+    fn read_box_header(buf: &[u8], offset: usize) -> (u32, [u8; 4]) {
+        let size = u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap());
+        let mut box_type = [0u8; 4];
+        box_type.copy_from_slice(&buf[offset + 4..offset + 8]);
+        (size, box_type)
+    }
+
+    #[test]
+    fn stbl_box_describes_every_packet_in_the_chunk() {
+        let mut codec_params = CodecParameters::default();
+        codec_params
+            .with_sample_rate(44_100)
+            .with_channels(symphonia::core::audio::Channels::FRONT_LEFT);
+
+        let (packets, chunk) = chunk_with_packets(&[50, 60, 70]);
+
+        let stbl = build_stbl_box(&codec_params, &packets, &chunk, 0x1000);
+
+        let (stbl_size, stbl_type) = read_box_header(&stbl, 0);
+        assert_eq!(&stbl_type, b"stbl");
+        assert_eq!(stbl_size as usize, stbl.len());
+
+        // Walk the child boxes and confirm stsz records one entry per packet with the
+        // exact byte length that went in
+        let mut offset = 8;
+        let mut stsz_content = None;
+        while offset < stbl.len() {
+            let (size, box_type) = read_box_header(&stbl, offset);
+            if &box_type == b"stsz" {
+                stsz_content = Some(stbl[offset + 8..offset + size as usize].to_vec());
+            }
+            offset += size as usize;
+        }
+
+        let stsz = stsz_content.expect("stsz box should be present");
+        let sample_count = u32::from_be_bytes(stsz[8..12].try_into().unwrap());
+        assert_eq!(sample_count as usize, packets.len());
+        for (i, &len) in [50usize, 60, 70].iter().enumerate() {
+            let entry_offset = 12 + i * 4;
+            let entry = u32::from_be_bytes(stsz[entry_offset..entry_offset + 4].try_into().unwrap());
+            assert_eq!(entry as usize, len);
+        }
+    }
+
+    #[test]
+    fn moov_box_nests_trak_and_reports_chunk_duration() {
+        let mut codec_params = CodecParameters::default();
+        codec_params
+            .with_sample_rate(44_100)
+            .with_channels(symphonia::core::audio::Channels::FRONT_LEFT);
+
+        let (packets, chunk) = chunk_with_packets(&[50, 60]);
+
+        let moov = build_moov_box(&codec_params, &packets, &chunk, 0x1000, None);
+
+        let (moov_size, moov_type) = read_box_header(&moov, 0);
+        assert_eq!(&moov_type, b"moov");
+        assert_eq!(moov_size as usize, moov.len());
+
+        // mvhd should be the first child, reporting the chunk's total sample duration
+        let (mvhd_size, mvhd_type) = read_box_header(&moov, 8);
+        assert_eq!(&mvhd_type, b"mvhd");
+        let mvhd_duration_offset = 8 + 8 + 16; // moov header + mvhd header + version/flags + 2 timestamps + timescale
+        let duration = u32::from_be_bytes(
+            moov[mvhd_duration_offset..mvhd_duration_offset + 4].try_into().unwrap(),
+        );
+        let expected_duration: u32 = chunk.packets.iter().map(|&i| packets[i].dur as u32).sum();
+        assert_eq!(duration, expected_duration);
+
+        // trak should follow mvhd as moov's next child
+        let (_, trak_type) = read_box_header(&moov, 8 + mvhd_size as usize);
+        assert_eq!(&trak_type, b"trak");
+    }
+}
@@ -42,18 +42,18 @@ fn split_mp3(input_path: &Path, chunk_duration: Duration, output_dir: &Path, pre
     // Probe the format
     let probed = symphonia::default::get_probe()
         .format(&hint, mss, &format_opts, &metadata_opts)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error probing format: {}", e)))?;
+        .map_err(|e| io::Error::other(format!("Error probing format: {}", e)))?;
     
     let mut format = probed.format;
     
     // Get the default track
     let track = format.default_track()
-        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No default track found"))?;
+        .ok_or_else(|| io::Error::other("No default track found"))?;
     
     // Get codec parameters and time base
     let codec_params = track.codec_params.clone();
     let time_base = codec_params.time_base
-        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No time base found"))?;
+        .ok_or_else(|| io::Error::other("No time base found"))?;
     
     // Read original ID3 tags
     let original_tag = Tag::read_from_path(input_path).ok();
@@ -78,7 +78,7 @@ fn split_mp3(input_path: &Path, chunk_duration: Duration, output_dir: &Path, pre
     }
     
     if packets.is_empty() {
-        return Err(io::Error::new(io::ErrorKind::Other, "No audio packets found"));
+        return Err(io::Error::other("No audio packets found"));
     }
     
     println!("Found {} packets, total duration: {:.2} seconds ({:.2} minutes)", 